@@ -1,5 +1,8 @@
 use std::str::FromStr;
 
+use chacha20::cipher::{NewStreamCipher, SyncStreamCipher, SyncStreamCipherSeek};
+use generic_array::GenericArray;
+
 use crate::error::{Error as OsshError, ErrorKind, OsshResult};
 use self::internal_impl::*;
 
@@ -15,12 +18,20 @@ pub enum Cipher {
     Aes192_Ctr,
     Aes256_Ctr,
     TDes_Cbc,
+    Aes128_Gcm,
+    Aes256_Gcm,
+    ChaCha20_Poly1305,
     Null,
 }
 
 impl Cipher {
+    /// Encrypt `src` with an unauthenticated cipher.
+    ///
+    /// AEAD ciphers (see [`Cipher::is_aead`]) carry a tag and cannot be
+    /// driven through this method; use [`Cipher::encrypt_aead`] instead.
     pub fn encrypt(self, src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
         use Cipher::*;
+        self.check_key_and_iv(key, iv)?;
         match self {
             Aes128_Cbc => aes128cbc_encrypt(src, key, iv),
             Aes192_Cbc => aes192cbc_encrypt(src, key, iv),
@@ -30,11 +41,22 @@ impl Cipher {
             Aes256_Ctr => aes256ctr_encrypt(src, key, iv),
             TDes_Cbc => tdescbc_encrypt(src, key, iv),
             Null => Ok(src.to_vec()),
+            Aes128_Gcm | Aes256_Gcm | ChaCha20_Poly1305 => Err(ErrorKind::UnsupportCipher.into()),
         }
     }
 
+    /// Decrypt `src` with an unauthenticated cipher.
+    ///
+    /// AEAD ciphers (see [`Cipher::is_aead`]) carry a tag and cannot be
+    /// driven through this method; use [`Cipher::decrypt_aead`] instead.
     pub fn decrypt(self, src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
         use Cipher::*;
+        self.check_key_and_iv(key, iv)?;
+        if matches!(self, Aes128_Cbc | Aes192_Cbc | Aes256_Cbc | TDes_Cbc)
+            && !src.len().is_multiple_of(self.block_size())
+        {
+            return Err(ErrorKind::Length.into());
+        }
         match self {
             Aes128_Cbc => aes128cbc_decrypt(src, key, iv),
             Aes192_Cbc => aes192cbc_decrypt(src, key, iv),
@@ -44,9 +66,84 @@ impl Cipher {
             Aes256_Ctr => aes256ctr_decrypt(src, key, iv),
             TDes_Cbc => tdescbc_decrypt(src, key, iv),
             Null => Ok(src.to_vec()),
+            Aes128_Gcm | Aes256_Gcm | ChaCha20_Poly1305 => Err(ErrorKind::UnsupportCipher.into()),
+        }
+    }
+
+    /// Encrypt `src` with an AEAD cipher, authenticating `aad` alongside it.
+    ///
+    /// Returns the ciphertext and the authentication tag (see
+    /// [`Cipher::tag_len`]) as separate buffers.
+    pub fn encrypt_aead(
+        self,
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+    ) -> OsshResult<(Vec<u8>, Vec<u8>)> {
+        use Cipher::*;
+        self.check_key_and_iv(key, iv)?;
+        match self {
+            Aes128_Gcm => aes128gcm_encrypt(src, key, iv, aad),
+            Aes256_Gcm => aes256gcm_encrypt(src, key, iv, aad),
+            ChaCha20_Poly1305 => chacha20poly1305_encrypt(src, key, iv, aad),
+            _ => Err(ErrorKind::UnsupportCipher.into()),
         }
     }
 
+    /// Decrypt `src` with an AEAD cipher, verifying it against `aad` and
+    /// `tag`.
+    ///
+    /// Fails closed: if the tag does not verify, an error is returned and no
+    /// plaintext is produced.
+    pub fn decrypt_aead(
+        self,
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+        tag: &[u8],
+    ) -> OsshResult<Vec<u8>> {
+        use Cipher::*;
+        self.check_key_and_iv(key, iv)?;
+        match self {
+            Aes128_Gcm => aes128gcm_decrypt(src, key, iv, aad, tag),
+            Aes256_Gcm => aes256gcm_decrypt(src, key, iv, aad, tag),
+            ChaCha20_Poly1305 => chacha20poly1305_decrypt(src, key, iv, aad, tag),
+            _ => Err(ErrorKind::UnsupportCipher.into()),
+        }
+    }
+
+    /// Check that `key` and `iv` have the exact lengths this cipher expects,
+    /// so a size mismatch surfaces as [`ErrorKind::KeyIvLength`] instead of
+    /// an opaque backend error or a panic.
+    fn check_key_and_iv(self, key: &[u8], iv: &[u8]) -> OsshResult<()> {
+        if key.len() != self.key_len() || iv.len() != self.iv_len() {
+            return Err(ErrorKind::KeyIvLength.into());
+        }
+        Ok(())
+    }
+
+    /// Open a streaming handle that encrypts data fed to it in chunks,
+    /// instead of requiring the whole plaintext up front.
+    ///
+    /// AEAD ciphers are not supported here yet; use
+    /// [`Cipher::encrypt_aead`] for those.
+    pub fn encryptor(self, key: &[u8], iv: &[u8]) -> OsshResult<CipherStream> {
+        self.check_key_and_iv(key, iv)?;
+        Ok(CipherStream(StreamState::new(self, key, iv, true)?))
+    }
+
+    /// Open a streaming handle that decrypts data fed to it in chunks,
+    /// instead of requiring the whole ciphertext up front.
+    ///
+    /// AEAD ciphers are not supported here yet; use
+    /// [`Cipher::decrypt_aead`] for those.
+    pub fn decryptor(self, key: &[u8], iv: &[u8]) -> OsshResult<CipherStream> {
+        self.check_key_and_iv(key, iv)?;
+        Ok(CipherStream(StreamState::new(self, key, iv, false)?))
+    }
+
     pub fn key_len(self) -> usize {
         use Cipher::*;
         match self {
@@ -57,6 +154,10 @@ impl Cipher {
             Aes192_Ctr => 24,
             Aes256_Ctr => 32,
             TDes_Cbc => 24,
+            Aes128_Gcm => 16,
+            Aes256_Gcm => 32,
+            // K_2 || K_1: one 32-byte key for the payload, one for the length field
+            ChaCha20_Poly1305 => 64,
             Null => 0,
         }
     }
@@ -71,6 +172,9 @@ impl Cipher {
             Aes192_Ctr => 16,
             Aes256_Ctr => 16,
             TDes_Cbc => 8,
+            Aes128_Gcm => 12,
+            Aes256_Gcm => 12,
+            ChaCha20_Poly1305 => 8,
             Null => 0,
         }
     }
@@ -85,10 +189,27 @@ impl Cipher {
             Aes192_Ctr => 16,
             Aes256_Ctr => 16,
             TDes_Cbc => 8,
+            Aes128_Gcm | Aes256_Gcm | ChaCha20_Poly1305 => 1,
             Null => 8,
         }
     }
 
+    /// The length in bytes of the authentication tag produced by this
+    /// cipher, or `0` if it is not an AEAD cipher.
+    pub fn tag_len(self) -> usize {
+        use Cipher::*;
+        match self {
+            Aes128_Gcm | Aes256_Gcm | ChaCha20_Poly1305 => 16,
+            _ => 0,
+        }
+    }
+
+    /// Whether this cipher authenticates its ciphertext and must be driven
+    /// through [`Cipher::encrypt_aead`]/[`Cipher::decrypt_aead`].
+    pub fn is_aead(self) -> bool {
+        self.tag_len() > 0
+    }
+
     pub fn name(self) -> &'static str {
         use Cipher::*;
         match self {
@@ -99,6 +220,9 @@ impl Cipher {
             Aes192_Ctr => "aes192-ctr",
             Aes256_Ctr => "aes256-ctr",
             TDes_Cbc => "3des-cbc",
+            Aes128_Gcm => "aes128-gcm@openssh.com",
+            Aes256_Gcm => "aes256-gcm@openssh.com",
+            ChaCha20_Poly1305 => "chacha20-poly1305@openssh.com",
             Null => "none",
         }
     }
@@ -106,6 +230,90 @@ impl Cipher {
     pub fn is_null(self) -> bool {
         self == Cipher::Null
     }
+
+    /// Configure this cipher's padding mode, returning a [`PaddedCipher`]
+    /// that can be encrypted/decrypted in place of `self`.
+    ///
+    /// Only the CBC and 3DES-CBC ciphers pad in the first place, so this is
+    /// a no-op for every other variant.
+    pub fn with_padding(self, padding: Padding) -> PaddedCipher {
+        PaddedCipher {
+            cipher: self,
+            padding,
+        }
+    }
+
+    fn check_block_aligned(self, src: &[u8]) -> OsshResult<()> {
+        if !src.len().is_multiple_of(self.block_size()) {
+            return Err(ErrorKind::Length.into());
+        }
+        Ok(())
+    }
+}
+
+/// How a block cipher pads its input to a multiple of its block size.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Padding {
+    /// Pad with PKCS#7 (the default used by [`Cipher::encrypt`]/
+    /// [`Cipher::decrypt`]).
+    Pkcs7,
+    /// Apply no padding at all; callers are responsible for block-aligning
+    /// their own input (e.g. OpenSSH's own encrypt-to-a-block-boundary
+    /// scheme inside the private-key envelope). Decrypting non-block-sized
+    /// input is rejected rather than silently truncated.
+    NoPadding,
+}
+
+/// A [`Cipher`] configured with an explicit [`Padding`] mode. See
+/// [`Cipher::with_padding`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PaddedCipher {
+    cipher: Cipher,
+    padding: Padding,
+}
+
+impl PaddedCipher {
+    pub fn encrypt(self, src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        use Cipher::*;
+        if self.padding == Padding::Pkcs7 {
+            return self.cipher.encrypt(src, key, iv);
+        }
+        self.cipher.check_key_and_iv(key, iv)?;
+        match self.cipher {
+            Aes128_Cbc | Aes192_Cbc | Aes256_Cbc | TDes_Cbc => {
+                self.cipher.check_block_aligned(src)?;
+            }
+            _ => {}
+        }
+        match self.cipher {
+            Aes128_Cbc => aes128cbc_encrypt_nopad(src, key, iv),
+            Aes192_Cbc => aes192cbc_encrypt_nopad(src, key, iv),
+            Aes256_Cbc => aes256cbc_encrypt_nopad(src, key, iv),
+            TDes_Cbc => tdescbc_encrypt_nopad(src, key, iv),
+            _ => self.cipher.encrypt(src, key, iv),
+        }
+    }
+
+    pub fn decrypt(self, src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        use Cipher::*;
+        if self.padding == Padding::Pkcs7 {
+            return self.cipher.decrypt(src, key, iv);
+        }
+        self.cipher.check_key_and_iv(key, iv)?;
+        match self.cipher {
+            Aes128_Cbc | Aes192_Cbc | Aes256_Cbc | TDes_Cbc => {
+                self.cipher.check_block_aligned(src)?;
+            }
+            _ => {}
+        }
+        match self.cipher {
+            Aes128_Cbc => aes128cbc_decrypt_nopad(src, key, iv),
+            Aes192_Cbc => aes192cbc_decrypt_nopad(src, key, iv),
+            Aes256_Cbc => aes256cbc_decrypt_nopad(src, key, iv),
+            TDes_Cbc => tdescbc_decrypt_nopad(src, key, iv),
+            _ => self.cipher.decrypt(src, key, iv),
+        }
+    }
 }
 
 impl FromStr for Cipher {
@@ -120,26 +328,62 @@ impl FromStr for Cipher {
             "aes128-ctr" => Ok(Aes128_Ctr),
             "aes192-ctr" => Ok(Aes192_Ctr),
             "aes256-ctr" => Ok(Aes256_Ctr),
+            "aes128-gcm@openssh.com" => Ok(Aes128_Gcm),
+            "aes256-gcm@openssh.com" => Ok(Aes256_Gcm),
+            "chacha20-poly1305@openssh.com" => Ok(ChaCha20_Poly1305),
             "none" => Ok(Null),
             _ => Err(ErrorKind::UnsupportCipher.into()),
         }
     }
 }
 
+/// A handle that encrypts or decrypts data fed to it in fixed-size chunks,
+/// rather than requiring the whole buffer up front. Obtained from
+/// [`Cipher::encryptor`]/[`Cipher::decryptor`].
+pub struct CipherStream(StreamState);
+
+impl CipherStream {
+    /// Process the next chunk of input, writing the produced bytes to the
+    /// front of `output` and returning how many bytes were written.
+    /// `output` must be at least `input.len() + cipher.block_size()` bytes.
+    pub fn update(&mut self, input: &[u8], output: &mut [u8]) -> OsshResult<usize> {
+        self.0.update(input, output)
+    }
+
+    /// Flush any buffered partial block (applying or removing padding) and
+    /// return how many bytes were written to `output`.
+    pub fn finalize(&mut self, output: &mut [u8]) -> OsshResult<usize> {
+        self.0.finalize(output)
+    }
+}
+
 #[cfg(not(feature = "openssl-cipher"))]
 mod internal_impl {
+    use std::marker::PhantomData;
+
     use aes::{Aes128, Aes192, Aes256};
     use aes_ctr::{Aes128Ctr, Aes192Ctr, Aes256Ctr};
-    use stream_cipher::{NewStreamCipher, SyncStreamCipher};
-    use block_modes::{block_padding::Pkcs7, BlockMode, Cbc};
+    use aes_gcm::{
+        aead::{generic_array::GenericArray, Aead, NewAead, Payload},
+        Aes128Gcm, Aes256Gcm,
+    };
+    use cipher::{BlockCipher, NewBlockCipher, NewStreamCipher, SyncStreamCipher};
+    use block_modes::{
+        block_padding::{NoPadding, Pkcs7},
+        BlockMode, Cbc,
+    };
     use des::TdesEde3;
 
-    use crate::error::OsshResult;
+    use crate::error::{Error as OsshError, ErrorKind, OsshResult};
 
     type Aes128Cbc = Cbc::<Aes128, Pkcs7>;
     type Aes192Cbc = Cbc::<Aes192, Pkcs7>;
     type Aes256Cbc = Cbc::<Aes256, Pkcs7>;
     type TdesCbc = Cbc::<TdesEde3, Pkcs7>;
+    type Aes128CbcNoPad = Cbc::<Aes128, NoPadding>;
+    type Aes192CbcNoPad = Cbc::<Aes192, NoPadding>;
+    type Aes256CbcNoPad = Cbc::<Aes256, NoPadding>;
+    type TdesCbcNoPad = Cbc::<TdesEde3, NoPadding>;
 
     pub fn aes128cbc_encrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
         Ok(Aes128Cbc::new_var(key, iv)?.encrypt_vec(src))
@@ -169,6 +413,34 @@ mod internal_impl {
         Ok(TdesCbc::new_var(key, iv)?.decrypt_vec(src)?)
     }
 
+    pub fn aes128cbc_encrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        Ok(Aes128CbcNoPad::new_var(key, iv)?.encrypt_vec(src))
+    }
+    pub fn aes128cbc_decrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        Ok(Aes128CbcNoPad::new_var(key, iv)?.decrypt_vec(src)?)
+    }
+
+    pub fn aes192cbc_encrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        Ok(Aes192CbcNoPad::new_var(key, iv)?.encrypt_vec(src))
+    }
+    pub fn aes192cbc_decrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        Ok(Aes192CbcNoPad::new_var(key, iv)?.decrypt_vec(src)?)
+    }
+
+    pub fn aes256cbc_encrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        Ok(Aes256CbcNoPad::new_var(key, iv)?.encrypt_vec(src))
+    }
+    pub fn aes256cbc_decrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        Ok(Aes256CbcNoPad::new_var(key, iv)?.decrypt_vec(src)?)
+    }
+
+    pub fn tdescbc_encrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        Ok(TdesCbcNoPad::new_var(key, iv)?.encrypt_vec(src))
+    }
+    pub fn tdescbc_decrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        Ok(TdesCbcNoPad::new_var(key, iv)?.decrypt_vec(src)?)
+    }
+
     pub fn aes128ctr_encrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
         let mut encrypted = Vec::from(src);
         Aes128Ctr::new_var(key, iv)?.apply_keystream(&mut encrypted);
@@ -201,13 +473,217 @@ mod internal_impl {
         Aes256Ctr::new_var(key, iv)?.apply_keystream(&mut decrypted);
         Ok(decrypted)
     }
+
+    pub fn aes128gcm_encrypt(
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+    ) -> OsshResult<(Vec<u8>, Vec<u8>)> {
+        let cipher = Aes128Gcm::new(GenericArray::from_slice(key));
+        gcm_encrypt(&cipher, src, iv, aad)
+    }
+    pub fn aes128gcm_decrypt(
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+        tag: &[u8],
+    ) -> OsshResult<Vec<u8>> {
+        let cipher = Aes128Gcm::new(GenericArray::from_slice(key));
+        gcm_decrypt(&cipher, src, iv, aad, tag)
+    }
+
+    pub fn aes256gcm_encrypt(
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+    ) -> OsshResult<(Vec<u8>, Vec<u8>)> {
+        let cipher = Aes256Gcm::new(GenericArray::from_slice(key));
+        gcm_encrypt(&cipher, src, iv, aad)
+    }
+    pub fn aes256gcm_decrypt(
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+        tag: &[u8],
+    ) -> OsshResult<Vec<u8>> {
+        let cipher = Aes256Gcm::new(GenericArray::from_slice(key));
+        gcm_decrypt(&cipher, src, iv, aad, tag)
+    }
+
+    fn gcm_encrypt<C: Aead>(
+        cipher: &C,
+        src: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+    ) -> OsshResult<(Vec<u8>, Vec<u8>)> {
+        let mut out = cipher
+            .encrypt(GenericArray::from_slice(iv), Payload { msg: src, aad })
+            .map_err(|_| OsshError::from(ErrorKind::InvalidTag))?;
+        let tag = out.split_off(out.len() - 16);
+        Ok((out, tag))
+    }
+
+    fn gcm_decrypt<C: Aead>(
+        cipher: &C,
+        src: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+        tag: &[u8],
+    ) -> OsshResult<Vec<u8>> {
+        let mut buf = Vec::with_capacity(src.len() + tag.len());
+        buf.extend_from_slice(src);
+        buf.extend_from_slice(tag);
+        cipher
+            .decrypt(GenericArray::from_slice(iv), Payload { msg: &buf, aad })
+            .map_err(|_| ErrorKind::InvalidTag.into())
+    }
+
+    /// Drives a block cipher in CBC mode one block at a time by re-deriving
+    /// a fresh `Cbc<C, NoPadding>` per block, seeded with the previous
+    /// ciphertext block as its IV. `block_modes` has no incremental API of
+    /// its own, but chaining single-block calls this way is equivalent and
+    /// lets callers feed arbitrarily large data through in fixed-size
+    /// chunks. Padding is only applied/removed on the final, buffered
+    /// partial block in `finalize`.
+    pub struct CbcStream<C: BlockCipher + NewBlockCipher> {
+        key: Vec<u8>,
+        prev_block: Vec<u8>,
+        buffer: Vec<u8>,
+        block_size: usize,
+        encrypting: bool,
+        _cipher: PhantomData<C>,
+    }
+
+    impl<C: BlockCipher + NewBlockCipher> CbcStream<C> {
+        pub fn new(block_size: usize, key: &[u8], iv: &[u8], encrypting: bool) -> Self {
+            Self {
+                key: key.to_vec(),
+                prev_block: iv.to_vec(),
+                buffer: Vec::new(),
+                block_size,
+                encrypting,
+                _cipher: PhantomData,
+            }
+        }
+
+        pub fn update(&mut self, input: &[u8], output: &mut [u8]) -> OsshResult<usize> {
+            self.buffer.extend_from_slice(input);
+            // Decrypting must always hold back the last full block: until
+            // `finalize` we don't know whether it's the one carrying the
+            // PKCS7 padding.
+            let keep = if self.encrypting { 0 } else { self.block_size };
+            let mut written = 0;
+            while self.buffer.len() >= keep + self.block_size {
+                let block: Vec<u8> = self.buffer.drain(..self.block_size).collect();
+                let out_block = if self.encrypting {
+                    Cbc::<C, NoPadding>::new_var(&self.key, &self.prev_block)?.encrypt_vec(&block)
+                } else {
+                    Cbc::<C, NoPadding>::new_var(&self.key, &self.prev_block)?.decrypt_vec(&block)?
+                };
+                self.prev_block = if self.encrypting {
+                    out_block.clone()
+                } else {
+                    block
+                };
+                output[written..written + self.block_size].copy_from_slice(&out_block);
+                written += self.block_size;
+            }
+            Ok(written)
+        }
+
+        pub fn finalize(&mut self, output: &mut [u8]) -> OsshResult<usize> {
+            if self.encrypting {
+                let out = Cbc::<C, Pkcs7>::new_var(&self.key, &self.prev_block)?.encrypt_vec(&self.buffer);
+                output[..out.len()].copy_from_slice(&out);
+                Ok(out.len())
+            } else {
+                if self.buffer.len() != self.block_size {
+                    return Err(ErrorKind::Length.into());
+                }
+                let out = Cbc::<C, Pkcs7>::new_var(&self.key, &self.prev_block)?.decrypt_vec(&self.buffer)?;
+                output[..out.len()].copy_from_slice(&out);
+                Ok(out.len())
+            }
+        }
+    }
+
+    fn ctr_update<C: SyncStreamCipher>(cipher: &mut C, input: &[u8], output: &mut [u8]) -> usize {
+        output[..input.len()].copy_from_slice(input);
+        cipher.apply_keystream(&mut output[..input.len()]);
+        input.len()
+    }
+
+    pub enum StreamState {
+        Aes128Cbc(CbcStream<Aes128>),
+        Aes192Cbc(CbcStream<Aes192>),
+        Aes256Cbc(CbcStream<Aes256>),
+        TdesCbc(CbcStream<TdesEde3>),
+        Aes128Ctr(Aes128Ctr),
+        Aes192Ctr(Aes192Ctr),
+        Aes256Ctr(Aes256Ctr),
+        Null,
+    }
+
+    impl StreamState {
+        pub fn new(
+            cipher: super::Cipher,
+            key: &[u8],
+            iv: &[u8],
+            encrypting: bool,
+        ) -> OsshResult<Self> {
+            use super::Cipher::*;
+            Ok(match cipher {
+                Aes128_Cbc => StreamState::Aes128Cbc(CbcStream::new(16, key, iv, encrypting)),
+                Aes192_Cbc => StreamState::Aes192Cbc(CbcStream::new(16, key, iv, encrypting)),
+                Aes256_Cbc => StreamState::Aes256Cbc(CbcStream::new(16, key, iv, encrypting)),
+                TDes_Cbc => StreamState::TdesCbc(CbcStream::new(8, key, iv, encrypting)),
+                Aes128_Ctr => StreamState::Aes128Ctr(Aes128Ctr::new_var(key, iv)?),
+                Aes192_Ctr => StreamState::Aes192Ctr(Aes192Ctr::new_var(key, iv)?),
+                Aes256_Ctr => StreamState::Aes256Ctr(Aes256Ctr::new_var(key, iv)?),
+                Null => StreamState::Null,
+                Aes128_Gcm | Aes256_Gcm | ChaCha20_Poly1305 => {
+                    return Err(ErrorKind::UnsupportCipher.into())
+                }
+            })
+        }
+
+        pub fn update(&mut self, input: &[u8], output: &mut [u8]) -> OsshResult<usize> {
+            match self {
+                StreamState::Aes128Cbc(s) => s.update(input, output),
+                StreamState::Aes192Cbc(s) => s.update(input, output),
+                StreamState::Aes256Cbc(s) => s.update(input, output),
+                StreamState::TdesCbc(s) => s.update(input, output),
+                StreamState::Aes128Ctr(c) => Ok(ctr_update(c, input, output)),
+                StreamState::Aes192Ctr(c) => Ok(ctr_update(c, input, output)),
+                StreamState::Aes256Ctr(c) => Ok(ctr_update(c, input, output)),
+                StreamState::Null => {
+                    output[..input.len()].copy_from_slice(input);
+                    Ok(input.len())
+                }
+            }
+        }
+
+        pub fn finalize(&mut self, output: &mut [u8]) -> OsshResult<usize> {
+            match self {
+                StreamState::Aes128Cbc(s) => s.finalize(output),
+                StreamState::Aes192Cbc(s) => s.finalize(output),
+                StreamState::Aes256Cbc(s) => s.finalize(output),
+                StreamState::TdesCbc(s) => s.finalize(output),
+                _ => Ok(0),
+            }
+        }
+    }
 }
 
 #[cfg(feature = "openssl-cipher")]
 mod internal_impl {
     use openssl::symm::{Cipher, Crypter, Mode};
 
-    use crate::error::OsshResult;
+    use crate::error::{ErrorKind, OsshResult};
 
     fn openssl_encrypt(cipher: Cipher, src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
         let mut crypt = Crypter::new(cipher, Mode::Encrypt, key, Some(iv))?;
@@ -222,57 +698,687 @@ mod internal_impl {
         let mut crypt = Crypter::new(cipher, Mode::Decrypt, key, Some(iv))?;
         let mut buf = vec![0; src.len() + cipher.block_size()];
         let mut n = crypt.update(src, &mut buf)?;
+        // A bad padding byte or corrupt final block surfaces here, not at
+        // `Crypter::new`, so it must not be folded into `KeyIvLength`.
+        n += crypt
+            .finalize(&mut buf[n..])
+            .map_err(|_| ErrorKind::Decrypt)?;
+        buf.truncate(n);
+        Ok(buf)
+    }
+
+    fn openssl_encrypt_nopad(cipher: Cipher, src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        let mut crypt = Crypter::new(cipher, Mode::Encrypt, key, Some(iv))?;
+        crypt.pad(false);
+        let mut buf = vec![0; src.len() + cipher.block_size()];
+        let mut n = crypt.update(src, &mut buf)?;
         n += crypt.finalize(&mut buf[n..])?;
         buf.truncate(n);
         Ok(buf)
     }
 
+    fn openssl_decrypt_nopad(cipher: Cipher, src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        let mut crypt = Crypter::new(cipher, Mode::Decrypt, key, Some(iv))?;
+        crypt.pad(false);
+        let mut buf = vec![0; src.len() + cipher.block_size()];
+        let mut n = crypt.update(src, &mut buf)?;
+        n += crypt
+            .finalize(&mut buf[n..])
+            .map_err(|_| ErrorKind::Decrypt)?;
+        buf.truncate(n);
+        Ok(buf)
+    }
+
     pub fn aes128cbc_encrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_encrypt(Cipher::aes_128_cbc(), src, key, iv)?)
+        openssl_encrypt(Cipher::aes_128_cbc(), src, key, iv)
     }
     pub fn aes128cbc_decrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_decrypt(Cipher::aes_128_cbc(), src, key, iv)?)
+        openssl_decrypt(Cipher::aes_128_cbc(), src, key, iv)
     }
 
     pub fn aes192cbc_encrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_encrypt(Cipher::aes_192_cbc(), src, key, iv)?)
+        openssl_encrypt(Cipher::aes_192_cbc(), src, key, iv)
     }
     pub fn aes192cbc_decrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_decrypt(Cipher::aes_192_cbc(), src, key, iv)?)
+        openssl_decrypt(Cipher::aes_192_cbc(), src, key, iv)
     }
 
     pub fn aes256cbc_encrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_encrypt(Cipher::aes_256_cbc(), src, key, iv)?)
+        openssl_encrypt(Cipher::aes_256_cbc(), src, key, iv)
     }
     pub fn aes256cbc_decrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_decrypt(Cipher::aes_256_cbc(), src, key, iv)?)
+        openssl_decrypt(Cipher::aes_256_cbc(), src, key, iv)
     }
 
     pub fn tdescbc_encrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_encrypt(Cipher::des_ede3_cbc(), src, key, iv)?)
+        openssl_encrypt(Cipher::des_ede3_cbc(), src, key, iv)
     }
     pub fn tdescbc_decrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_decrypt(Cipher::des_ede3_cbc(), src, key, iv)?)
+        openssl_decrypt(Cipher::des_ede3_cbc(), src, key, iv)
+    }
+
+    pub fn aes128cbc_encrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        openssl_encrypt_nopad(Cipher::aes_128_cbc(), src, key, iv)
+    }
+    pub fn aes128cbc_decrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        openssl_decrypt_nopad(Cipher::aes_128_cbc(), src, key, iv)
+    }
+
+    pub fn aes192cbc_encrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        openssl_encrypt_nopad(Cipher::aes_192_cbc(), src, key, iv)
+    }
+    pub fn aes192cbc_decrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        openssl_decrypt_nopad(Cipher::aes_192_cbc(), src, key, iv)
+    }
+
+    pub fn aes256cbc_encrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        openssl_encrypt_nopad(Cipher::aes_256_cbc(), src, key, iv)
+    }
+    pub fn aes256cbc_decrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        openssl_decrypt_nopad(Cipher::aes_256_cbc(), src, key, iv)
+    }
+
+    pub fn tdescbc_encrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        openssl_encrypt_nopad(Cipher::des_ede3_cbc(), src, key, iv)
+    }
+    pub fn tdescbc_decrypt_nopad(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
+        openssl_decrypt_nopad(Cipher::des_ede3_cbc(), src, key, iv)
     }
 
     pub fn aes128ctr_encrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_encrypt(Cipher::aes_128_ctr(), src, key, iv)?)
+        openssl_encrypt(Cipher::aes_128_ctr(), src, key, iv)
     }
     pub fn aes128ctr_decrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_decrypt(Cipher::aes_128_ctr(), src, key, iv)?)
+        openssl_decrypt(Cipher::aes_128_ctr(), src, key, iv)
     }
 
     pub fn aes192ctr_encrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_encrypt(Cipher::aes_192_ctr(), src, key, iv)?)
+        openssl_encrypt(Cipher::aes_192_ctr(), src, key, iv)
     }
     pub fn aes192ctr_decrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_decrypt(Cipher::aes_192_ctr(), src, key, iv)?)
+        openssl_decrypt(Cipher::aes_192_ctr(), src, key, iv)
     }
 
     pub fn aes256ctr_encrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_encrypt(Cipher::aes_256_ctr(), src, key, iv)?)
+        openssl_encrypt(Cipher::aes_256_ctr(), src, key, iv)
     }
     pub fn aes256ctr_decrypt(src: &[u8], key: &[u8], iv: &[u8]) -> OsshResult<Vec<u8>> {
-        Ok(openssl_decrypt(Cipher::aes_256_ctr(), src, key, iv)?)
+        openssl_decrypt(Cipher::aes_256_ctr(), src, key, iv)
+    }
+
+    fn openssl_aead_encrypt(
+        cipher: Cipher,
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+        tag_len: usize,
+    ) -> OsshResult<(Vec<u8>, Vec<u8>)> {
+        let mut crypt = Crypter::new(cipher, Mode::Encrypt, key, Some(iv))?;
+        crypt.aad_update(aad)?;
+        let mut buf = vec![0; src.len() + cipher.block_size()];
+        let mut n = crypt.update(src, &mut buf)?;
+        n += crypt.finalize(&mut buf[n..])?;
+        buf.truncate(n);
+        let mut tag = vec![0; tag_len];
+        crypt.get_tag(&mut tag)?;
+        Ok((buf, tag))
+    }
+
+    fn openssl_aead_decrypt(
+        cipher: Cipher,
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+        tag: &[u8],
+    ) -> OsshResult<Vec<u8>> {
+        let mut crypt = Crypter::new(cipher, Mode::Decrypt, key, Some(iv))?;
+        crypt.aad_update(aad)?;
+        let mut buf = vec![0; src.len() + cipher.block_size()];
+        let mut n = crypt.update(src, &mut buf)?;
+        // The tag must be set before `finalize` so the decrypt call can
+        // verify it and fail closed instead of returning unauthenticated
+        // plaintext.
+        crypt.set_tag(tag)?;
+        n += crypt
+            .finalize(&mut buf[n..])
+            .map_err(|_| ErrorKind::InvalidTag)?;
+        buf.truncate(n);
+        Ok(buf)
+    }
+
+    pub fn aes128gcm_encrypt(
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+    ) -> OsshResult<(Vec<u8>, Vec<u8>)> {
+        openssl_aead_encrypt(Cipher::aes_128_gcm(), src, key, iv, aad, 16)
+    }
+    pub fn aes128gcm_decrypt(
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+        tag: &[u8],
+    ) -> OsshResult<Vec<u8>> {
+        openssl_aead_decrypt(Cipher::aes_128_gcm(), src, key, iv, aad, tag)
+    }
+
+    pub fn aes256gcm_encrypt(
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+    ) -> OsshResult<(Vec<u8>, Vec<u8>)> {
+        openssl_aead_encrypt(Cipher::aes_256_gcm(), src, key, iv, aad, 16)
+    }
+    pub fn aes256gcm_decrypt(
+        src: &[u8],
+        key: &[u8],
+        iv: &[u8],
+        aad: &[u8],
+        tag: &[u8],
+    ) -> OsshResult<Vec<u8>> {
+        openssl_aead_decrypt(Cipher::aes_256_gcm(), src, key, iv, aad, tag)
+    }
+
+    fn to_openssl_cipher(cipher: super::Cipher) -> OsshResult<Cipher> {
+        use super::Cipher::*;
+        Ok(match cipher {
+            Aes128_Cbc => Cipher::aes_128_cbc(),
+            Aes192_Cbc => Cipher::aes_192_cbc(),
+            Aes256_Cbc => Cipher::aes_256_cbc(),
+            TDes_Cbc => Cipher::des_ede3_cbc(),
+            Aes128_Ctr => Cipher::aes_128_ctr(),
+            Aes192_Ctr => Cipher::aes_192_ctr(),
+            Aes256_Ctr => Cipher::aes_256_ctr(),
+            Null | Aes128_Gcm | Aes256_Gcm | ChaCha20_Poly1305 => {
+                return Err(ErrorKind::UnsupportCipher.into())
+            }
+        })
     }
-}
\ No newline at end of file
+
+    pub enum StreamState {
+        Crypter { crypter: Crypter, encrypting: bool },
+        Null,
+    }
+
+    impl StreamState {
+        pub fn new(
+            cipher: super::Cipher,
+            key: &[u8],
+            iv: &[u8],
+            encrypting: bool,
+        ) -> OsshResult<Self> {
+            if cipher.is_null() {
+                return Ok(StreamState::Null);
+            }
+            let oc = to_openssl_cipher(cipher)?;
+            let mode = if encrypting { Mode::Encrypt } else { Mode::Decrypt };
+            Ok(StreamState::Crypter {
+                crypter: Crypter::new(oc, mode, key, Some(iv))?,
+                encrypting,
+            })
+        }
+
+        pub fn update(&mut self, input: &[u8], output: &mut [u8]) -> OsshResult<usize> {
+            match self {
+                StreamState::Crypter { crypter, .. } => Ok(crypter.update(input, output)?),
+                StreamState::Null => {
+                    output[..input.len()].copy_from_slice(input);
+                    Ok(input.len())
+                }
+            }
+        }
+
+        pub fn finalize(&mut self, output: &mut [u8]) -> OsshResult<usize> {
+            match self {
+                // A bad final block (wrong padding, corrupt ciphertext)
+                // surfaces here on decrypt; don't fold it into KeyIvLength.
+                StreamState::Crypter {
+                    crypter,
+                    encrypting: false,
+                } => Ok(crypter
+                    .finalize(output)
+                    .map_err(|_| ErrorKind::Decrypt)?),
+                StreamState::Crypter { crypter, .. } => Ok(crypter.finalize(output)?),
+                StreamState::Null => Ok(0),
+            }
+        }
+    }
+}
+
+/// The `chacha20-poly1305@openssh.com` AEAD construction.
+///
+/// This is not a thin wrapper over either backend's bulk cipher API: OpenSSH
+/// splits the 64-byte key into `K_1`/`K_2`, encrypts the 4-byte packet
+/// length with `K_1` at block counter 0, derives the Poly1305 one-time key
+/// from `ChaCha20(K_2, nonce, counter=0)`, and encrypts the payload with
+/// `K_2` starting at counter 1. Both halves of the construction are
+/// independent of which backend is in use for the other ciphers, so it is
+/// implemented once here on top of the RustCrypto primitives and used
+/// whichever `internal_impl` backend is compiled in.
+///
+/// `src` is expected to be the 4-byte packet length followed by the
+/// payload; the returned ciphertext has the same layout.
+fn chacha20poly1305_encrypt(
+    src: &[u8],
+    key: &[u8],
+    iv: &[u8],
+    aad: &[u8],
+) -> OsshResult<(Vec<u8>, Vec<u8>)> {
+    if src.len() < 4 {
+        return Err(ErrorKind::Length.into());
+    }
+    let (len_field, payload) = src.split_at(4);
+    // K_1 (header key) is the *second* 32 bytes of the key material and
+    // K_2 (main key) is the first, per OpenSSH's PROTOCOL.chacha20poly1305.
+    let (k2, k1) = key.split_at(32);
+
+    let mut encrypted_len = len_field.to_vec();
+    chacha20_legacy(k1, iv, 0).apply_keystream(&mut encrypted_len);
+
+    let poly_key = poly1305_one_time_key(k2, iv);
+
+    let mut ciphertext = encrypted_len;
+    let mut encrypted_payload = payload.to_vec();
+    chacha20_legacy(k2, iv, 1).apply_keystream(&mut encrypted_payload);
+    ciphertext.extend_from_slice(&encrypted_payload);
+
+    let tag = poly1305_tag(&poly_key, aad, &ciphertext);
+    Ok((ciphertext, tag))
+}
+
+/// The inverse of [`chacha20poly1305_encrypt`]; verifies `tag` before
+/// decrypting anything, so a bad tag never yields plaintext.
+fn chacha20poly1305_decrypt(
+    src: &[u8],
+    key: &[u8],
+    iv: &[u8],
+    aad: &[u8],
+    tag: &[u8],
+) -> OsshResult<Vec<u8>> {
+    use subtle::ConstantTimeEq;
+
+    if src.len() < 4 {
+        return Err(ErrorKind::Length.into());
+    }
+    let (len_field, payload) = src.split_at(4);
+    let (k2, k1) = key.split_at(32);
+
+    let poly_key = poly1305_one_time_key(k2, iv);
+    let expected_tag = poly1305_tag(&poly_key, aad, src);
+    if expected_tag.ct_eq(tag).unwrap_u8() != 1 {
+        return Err(ErrorKind::InvalidTag.into());
+    }
+
+    let mut decrypted_len = len_field.to_vec();
+    chacha20_legacy(k1, iv, 0).apply_keystream(&mut decrypted_len);
+
+    let mut decrypted_payload = payload.to_vec();
+    chacha20_legacy(k2, iv, 1).apply_keystream(&mut decrypted_payload);
+
+    let mut out = decrypted_len;
+    out.extend_from_slice(&decrypted_payload);
+    Ok(out)
+}
+
+fn chacha20_legacy(key: &[u8], nonce: &[u8], block_counter: u64) -> chacha20::ChaCha20Legacy {
+    let mut cipher =
+        chacha20::ChaCha20Legacy::new(GenericArray::from_slice(key), GenericArray::from_slice(nonce));
+    cipher.seek(block_counter * 64);
+    cipher
+}
+
+fn poly1305_one_time_key(k2: &[u8], iv: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; 64];
+    chacha20_legacy(k2, iv, 0).apply_keystream(&mut block);
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&block[..32]);
+    key
+}
+
+/// Raw (unpadded) Poly1305 over `aad` followed by `ciphertext`, as used by
+/// `chacha20-poly1305@openssh.com`.
+///
+/// This is *not* the padded-to-16-bytes construction used by AEAD
+/// constructions like XChaCha20-Poly1305: a non-block-sized final chunk gets
+/// a single `0x01` delimiter byte appended rather than being zero-padded out
+/// to a full block, which is what [`Poly1305::compute_unpadded`] does and
+/// `update_padded` does not.
+fn poly1305_tag(key: &[u8; 32], aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    use poly1305::universal_hash::NewUniversalHash;
+    use poly1305::Poly1305;
+
+    let mac = Poly1305::new(GenericArray::from_slice(key));
+    let mut message = Vec::with_capacity(aad.len() + ciphertext.len());
+    message.extend_from_slice(aad);
+    message.extend_from_slice(ciphertext);
+    mac.compute_unpadded(&message).into_bytes().to_vec()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 8439 §2.5.2 Poly1305 test vector. The message is 34 bytes, i.e.
+    /// not a multiple of the 16-byte block size, so this pins down the
+    /// unpadded (not zero-padded) final-block behaviour `poly1305_tag`
+    /// relies on.
+    #[test]
+    fn poly1305_tag_matches_rfc8439_vector() {
+        let key: [u8; 32] = [
+            0x85, 0xd6, 0xbe, 0x78, 0x57, 0x55, 0x6d, 0x33, 0x7f, 0x44, 0x52, 0xfe, 0x42, 0xd5,
+            0x06, 0xa8, 0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf, 0xf6, 0xaf,
+            0x41, 0x49, 0xf5, 0x1b,
+        ];
+        let message = b"Cryptographic Forum Research Group";
+        let expected: [u8; 16] = [
+            0xa8, 0x06, 0x1d, 0xc1, 0x30, 0x51, 0x36, 0xc6, 0xc2, 0x2b, 0x8b, 0xaf, 0x0c, 0x01,
+            0x27, 0xa9,
+        ];
+        assert_eq!(poly1305_tag(&key, &[], message), expected);
+    }
+
+    /// NIST SP 800-38D AES-128-GCM test vector (all-zero key/IV, a single
+    /// zero plaintext block, no AAD).
+    #[test]
+    fn aes128_gcm_nist_kat() {
+        let key = [0u8; 16];
+        let iv = [0u8; 12];
+        let plaintext = [0u8; 16];
+        let expected_ciphertext = [
+            0x03, 0x88, 0xda, 0xce, 0x60, 0xb6, 0xa3, 0x92, 0xf3, 0x28, 0xc2, 0xb9, 0x71, 0xb2,
+            0xfe, 0x78,
+        ];
+        let expected_tag = [
+            0xab, 0x6e, 0x47, 0xd4, 0x2c, 0xec, 0x13, 0xbd, 0xf5, 0x3a, 0x67, 0xb2, 0x12, 0x57,
+            0xbd, 0xdf,
+        ];
+
+        let (ciphertext, tag) = Cipher::Aes128_Gcm
+            .encrypt_aead(&plaintext, &key, &iv, &[])
+            .unwrap();
+        assert_eq!(ciphertext, expected_ciphertext);
+        assert_eq!(tag, expected_tag);
+
+        let decrypted = Cipher::Aes128_Gcm
+            .decrypt_aead(&ciphertext, &key, &iv, &[], &tag)
+            .unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    /// Tampering with an AEAD tag must fail closed, not yield plaintext.
+    #[test]
+    fn aes256_gcm_rejects_bad_tag() {
+        let key = [0x42u8; 32];
+        let iv = [0x24u8; 12];
+        let plaintext = b"the quick brown fox jumps over the lazy dog";
+        let aad = b"packet header";
+
+        let (ciphertext, mut tag) = Cipher::Aes256_Gcm
+            .encrypt_aead(plaintext, &key, &iv, aad)
+            .unwrap();
+        let decrypted = Cipher::Aes256_Gcm
+            .decrypt_aead(&ciphertext, &key, &iv, aad, &tag)
+            .unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        tag[0] ^= 0xff;
+        let err = Cipher::Aes256_Gcm
+            .decrypt_aead(&ciphertext, &key, &iv, aad, &tag)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidTag);
+    }
+
+    /// A real OpenSSH `chacha20-poly1305@openssh.com` packet - an
+    /// SSH_MSG_SERVICE_REQUEST for "ssh-userauth", used as a known-answer
+    /// vector by the independent `ssh-cipher` crate - pinned against the
+    /// payload/Poly1305 half of the construction: the key that derives the
+    /// Poly1305 one-time key and encrypts the payload starting at block
+    /// counter 1 (not the key used for the length field, which this vector
+    /// doesn't fix). Unlike `chacha20_poly1305_openssh_round_trip`, which
+    /// is only self-consistent, this pins `chacha20_legacy`,
+    /// `poly1305_one_time_key` and `poly1305_tag` against what OpenSSH
+    /// actually produces. It calls them directly with K_2, so it does not
+    /// check which half of the 64-byte key `chacha20poly1305_encrypt`
+    /// passes to each of them.
+    #[test]
+    fn chacha20poly1305_body_matches_openssh_packet_vector() {
+        let k2: [u8; 32] = [
+            0x37, 0x9a, 0x8c, 0xa9, 0xe7, 0xe7, 0x05, 0x76, 0x36, 0x33, 0x21, 0x35, 0x11, 0xe8,
+            0xd9, 0x2e, 0xb1, 0x48, 0xa4, 0x6f, 0x1d, 0xd0, 0x04, 0x5e, 0xc8, 0x16, 0x4e, 0x5d,
+            0x23, 0xe4, 0x56, 0xeb,
+        ];
+        let nonce = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03];
+        // The encrypted 4-byte packet length field, authenticated as AAD
+        // but encrypted under K_1, which this vector doesn't exercise.
+        let aad = [0x57, 0x09, 0xdb, 0x2d];
+        let plaintext: [u8; 24] = [
+            0x06, 0x05, 0x00, 0x00, 0x00, 0x0c, 0x73, 0x73, 0x68, 0x2d, 0x75, 0x73, 0x65, 0x72,
+            0x61, 0x75, 0x74, 0x68, 0xde, 0x59, 0x49, 0xab, 0x06, 0x1f,
+        ];
+        let expected_ciphertext: [u8; 24] = [
+            0x6d, 0xcf, 0xb0, 0x3b, 0xe8, 0xa5, 0x5e, 0x7f, 0x02, 0x20, 0x46, 0x56, 0x72, 0xed,
+            0xd9, 0x21, 0x48, 0x9e, 0xa0, 0x17, 0x11, 0x98, 0xe8, 0xa7,
+        ];
+        let expected_tag: [u8; 16] = [
+            0x3e, 0x82, 0xfe, 0x0a, 0x2d, 0xb7, 0x12, 0x8d, 0x58, 0xef, 0x8d, 0x90, 0x47, 0x96,
+            0x3c, 0xa3,
+        ];
+
+        let poly_key = poly1305_one_time_key(&k2, &nonce);
+        let mut ciphertext = plaintext;
+        chacha20_legacy(&k2, &nonce, 1).apply_keystream(&mut ciphertext);
+        assert_eq!(ciphertext, expected_ciphertext);
+
+        let tag = poly1305_tag(&poly_key, &aad, &ciphertext);
+        assert_eq!(tag, expected_tag);
+
+        // And the inverse: decrypting the known ciphertext recovers the
+        // known plaintext.
+        let mut decrypted = expected_ciphertext;
+        chacha20_legacy(&k2, &nonce, 1).apply_keystream(&mut decrypted);
+        assert_eq!(decrypted, plaintext);
+    }
+
+    /// Round-trips `chacha20-poly1305@openssh.com` across a range of
+    /// payload lengths that straddle the 16-byte Poly1305 block boundary,
+    /// and confirms a corrupted tag/AAD/ciphertext is rejected.
+    #[test]
+    fn chacha20_poly1305_openssh_round_trip() {
+        let key = [0x7a_u8; 64];
+        let iv = [0x11_u8; 8];
+        let aad = b"aad";
+
+        for payload_len in [0usize, 1, 15, 16, 17, 31, 32, 33, 100] {
+            let mut src = vec![0u8, 0, 0, payload_len as u8];
+            src.extend(std::iter::repeat_n(0x5a, payload_len));
+
+            let (ciphertext, tag) = Cipher::ChaCha20_Poly1305
+                .encrypt_aead(&src, &key, &iv, aad)
+                .unwrap();
+            let decrypted = Cipher::ChaCha20_Poly1305
+                .decrypt_aead(&ciphertext, &key, &iv, aad, &tag)
+                .unwrap();
+            assert_eq!(decrypted, src, "payload_len={payload_len}");
+
+            let mut bad_tag = tag.clone();
+            bad_tag[0] ^= 0xff;
+            let err = Cipher::ChaCha20_Poly1305
+                .decrypt_aead(&ciphertext, &key, &iv, aad, &bad_tag)
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidTag, "payload_len={payload_len}");
+
+            let err = Cipher::ChaCha20_Poly1305
+                .decrypt_aead(&ciphertext, &key, &iv, b"aaD", &tag)
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidTag, "payload_len={payload_len}");
+
+            let mut bad_ciphertext = ciphertext.clone();
+            bad_ciphertext[0] ^= 0xff;
+            let err = Cipher::ChaCha20_Poly1305
+                .decrypt_aead(&bad_ciphertext, &key, &iv, aad, &tag)
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidTag, "payload_len={payload_len}");
+        }
+    }
+
+    /// A ciphertext shorter than the 4-byte length field must be rejected,
+    /// not panic.
+    #[test]
+    fn chacha20_poly1305_openssh_rejects_short_input() {
+        let key = [0u8; 64];
+        let iv = [0u8; 8];
+        let err = Cipher::ChaCha20_Poly1305
+            .encrypt_aead(&[1, 2, 3], &key, &iv, &[])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Length);
+
+        let err = Cipher::ChaCha20_Poly1305
+            .decrypt_aead(&[1, 2, 3], &key, &iv, &[], &[0u8; 16])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Length);
+    }
+
+    /// [`CipherStream`] must reassemble to the same output as the bulk
+    /// [`Cipher::encrypt`]/[`Cipher::decrypt`] API even when fed in chunks
+    /// that don't line up with the cipher's block size.
+    #[test]
+    fn cipher_stream_chunk_boundary_round_trip() {
+        let key = [0x5au8; 16];
+        let iv = [0x01u8; 16];
+        let plaintext: Vec<u8> = (0..200).map(|i| i as u8).collect();
+
+        let expected = Cipher::Aes128_Ctr.encrypt(&plaintext, &key, &iv).unwrap();
+
+        let mut encryptor = Cipher::Aes128_Ctr.encryptor(&key, &iv).unwrap();
+        let mut actual = Vec::new();
+        let mut out = [0u8; 256];
+        // Deliberately chunk sizes that don't align with the 16-byte block
+        // size (1, 16, 17, then the remainder).
+        for chunk in [&plaintext[0..1], &plaintext[1..17], &plaintext[17..34]] {
+            let n = encryptor.update(chunk, &mut out).unwrap();
+            actual.extend_from_slice(&out[..n]);
+        }
+        let n = encryptor.update(&plaintext[34..], &mut out).unwrap();
+        actual.extend_from_slice(&out[..n]);
+        let n = encryptor.finalize(&mut out).unwrap();
+        actual.extend_from_slice(&out[..n]);
+
+        assert_eq!(actual, expected);
+
+        let mut decryptor = Cipher::Aes128_Ctr.decryptor(&key, &iv).unwrap();
+        let mut roundtripped = Vec::new();
+        for chunk in [&actual[0..1], &actual[1..17], &actual[17..34], &actual[34..]] {
+            let n = decryptor.update(chunk, &mut out).unwrap();
+            roundtripped.extend_from_slice(&out[..n]);
+        }
+        let n = decryptor.finalize(&mut out).unwrap();
+        roundtripped.extend_from_slice(&out[..n]);
+        assert_eq!(roundtripped, plaintext);
+    }
+
+    /// Exercises the parts of [`CipherStream`] that [`Aes128_Ctr`] can't:
+    /// `CbcStream`'s per-block IV re-derivation and its "hold back one
+    /// block" decrypt logic, cross-checked against the one-shot
+    /// [`Cipher::encrypt`]/[`Cipher::decrypt`] API.
+    ///
+    /// [`Aes128_Ctr`]: Cipher::Aes128_Ctr
+    #[test]
+    fn cbc_cipher_stream_chunk_boundary_round_trip() {
+        let key = [0x5au8; 16];
+        let iv = [0x01u8; 16];
+        let plaintext: Vec<u8> = (0..200).map(|i| i as u8).collect();
+
+        let expected = Cipher::Aes128_Cbc.encrypt(&plaintext, &key, &iv).unwrap();
+
+        let mut encryptor = Cipher::Aes128_Cbc.encryptor(&key, &iv).unwrap();
+        let mut actual = Vec::new();
+        let mut out = [0u8; 256];
+        // Deliberately chunk sizes that don't align with the 16-byte block
+        // size (1, 16, 17, then the remainder).
+        for chunk in [&plaintext[0..1], &plaintext[1..17], &plaintext[17..34]] {
+            let n = encryptor.update(chunk, &mut out).unwrap();
+            actual.extend_from_slice(&out[..n]);
+        }
+        let n = encryptor.update(&plaintext[34..], &mut out).unwrap();
+        actual.extend_from_slice(&out[..n]);
+        let n = encryptor.finalize(&mut out).unwrap();
+        actual.extend_from_slice(&out[..n]);
+
+        assert_eq!(actual, expected);
+
+        let expected_plaintext = Cipher::Aes128_Cbc.decrypt(&actual, &key, &iv).unwrap();
+
+        let mut decryptor = Cipher::Aes128_Cbc.decryptor(&key, &iv).unwrap();
+        let mut roundtripped = Vec::new();
+        for chunk in [&actual[0..1], &actual[1..17], &actual[17..34], &actual[34..]] {
+            let n = decryptor.update(chunk, &mut out).unwrap();
+            roundtripped.extend_from_slice(&out[..n]);
+        }
+        let n = decryptor.finalize(&mut out).unwrap();
+        roundtripped.extend_from_slice(&out[..n]);
+
+        assert_eq!(roundtripped, expected_plaintext);
+        assert_eq!(roundtripped, plaintext);
+    }
+
+    /// A streaming decrypt that ends on a short, non-block-aligned final
+    /// chunk must fail closed at `finalize` rather than panicking or
+    /// silently truncating the output.
+    #[test]
+    fn cbc_cipher_stream_rejects_bad_final_block() {
+        let key = [0x5au8; 16];
+        let iv = [0x01u8; 16];
+        let plaintext = [0x42u8; 32];
+
+        let ciphertext = Cipher::Aes128_Cbc.encrypt(&plaintext, &key, &iv).unwrap();
+
+        let mut decryptor = Cipher::Aes128_Cbc.decryptor(&key, &iv).unwrap();
+        let mut out = [0u8; 64];
+        // Withhold the last byte of ciphertext, so whatever the backend
+        // still has buffered once `finalize` runs is not a whole number of
+        // blocks.
+        decryptor
+            .update(&ciphertext[..ciphertext.len() - 1], &mut out)
+            .unwrap();
+
+        decryptor.finalize(&mut out).unwrap_err();
+    }
+
+    /// `with_padding(NoPadding)` must reject non-block-aligned input rather
+    /// than silently truncating or padding it.
+    #[test]
+    fn with_padding_no_padding_rejects_unaligned_input() {
+        let key = [0u8; 16];
+        let iv = [0u8; 16];
+
+        let err = Cipher::Aes128_Cbc
+            .with_padding(Padding::NoPadding)
+            .encrypt(&[1, 2, 3], &key, &iv)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Length);
+
+        let err = Cipher::Aes128_Cbc
+            .with_padding(Padding::NoPadding)
+            .decrypt(&[1, 2, 3], &key, &iv)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Length);
+
+        // A block-aligned buffer round-trips cleanly.
+        let aligned = [0x42u8; 32];
+        let ciphertext = Cipher::Aes128_Cbc
+            .with_padding(Padding::NoPadding)
+            .encrypt(&aligned, &key, &iv)
+            .unwrap();
+        let plaintext = Cipher::Aes128_Cbc
+            .with_padding(Padding::NoPadding)
+            .decrypt(&ciphertext, &key, &iv)
+            .unwrap();
+        assert_eq!(plaintext, aligned);
+    }
+}
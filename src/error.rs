@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// A specialized [`Result`] type for this crate's operations.
+pub type OsshResult<T> = Result<T, Error>;
+
+/// The error type for this crate's operations.
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+}
+
+impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ErrorKind::UnsupportCipher => write!(f, "unsupported cipher"),
+            ErrorKind::Length => write!(f, "input length is not valid for this cipher"),
+            ErrorKind::KeyIvLength => write!(f, "key or IV has the wrong length for this cipher"),
+            ErrorKind::InvalidTag => write!(f, "authentication tag verification failed"),
+            ErrorKind::Decrypt => write!(f, "decryption failed (bad padding or corrupt ciphertext)"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The kind of error that occurred.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The requested cipher is not supported by this crate or by the
+    /// method being called.
+    UnsupportCipher,
+    /// Input had a length the cipher cannot process: not a multiple of a
+    /// block cipher's block size, or shorter than the 4-byte length field
+    /// of `chacha20-poly1305@openssh.com`.
+    Length,
+    /// The supplied key or IV does not match the length this cipher
+    /// expects.
+    KeyIvLength,
+    /// AEAD tag verification failed; no plaintext is produced.
+    InvalidTag,
+    /// A block cipher backend rejected the ciphertext during decryption
+    /// (bad padding or a corrupt block), as opposed to a malformed key or
+    /// IV. Distinct from [`ErrorKind::KeyIvLength`] so callers can tell a
+    /// wrong password/corrupt blob apart from a wrong key size.
+    Decrypt,
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Error { kind }
+    }
+}
+
+impl From<block_modes::InvalidKeyIvLength> for Error {
+    fn from(_: block_modes::InvalidKeyIvLength) -> Self {
+        ErrorKind::KeyIvLength.into()
+    }
+}
+
+impl From<block_modes::BlockModeError> for Error {
+    fn from(_: block_modes::BlockModeError) -> Self {
+        ErrorKind::Decrypt.into()
+    }
+}
+
+impl From<cipher::stream::InvalidKeyNonceLength> for Error {
+    fn from(_: cipher::stream::InvalidKeyNonceLength) -> Self {
+        ErrorKind::KeyIvLength.into()
+    }
+}
+
+#[cfg(feature = "openssl-cipher")]
+impl From<openssl::error::ErrorStack> for Error {
+    fn from(_: openssl::error::ErrorStack) -> Self {
+        ErrorKind::KeyIvLength.into()
+    }
+}